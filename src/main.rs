@@ -1,8 +1,9 @@
 #[macro_use]
 extern crate lazy_static;
-use rand::Rng;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 use regex::Regex;
-use std::{fmt::Display, num::ParseIntError, str::FromStr};
+use std::{collections::BTreeMap, fmt::Display, num::ParseIntError, str::FromStr};
 use structopt::StructOpt;
 
 #[derive(Clone, Debug, StructOpt)]
@@ -13,8 +14,17 @@ struct Opts {
     #[structopt(short, long)]
     aggregate: Option<Aggregate>,
 
-    /// Dice to roll. Eg. "d6", "5d10" etc
-    dice: Vec<Dice>,
+    /// Seed the PRNG so an entire invocation is reproducible.
+    #[structopt(short, long)]
+    seed: Option<u64>,
+
+    /// Print the exact probability distribution and summary stats instead of
+    /// rolling.
+    #[structopt(short, long)]
+    distribution: bool,
+
+    /// Dice expressions to roll. Eg. "d6", "5d10", "2d6 + 3*1d4 - d20"
+    expressions: Vec<Expr>,
 }
 
 #[derive(Copy, Clone, Debug)]
@@ -52,34 +62,237 @@ impl Display for ParseAggregateError {
     }
 }
 
+/// A keep/drop selection applied to the faces of a single dice term, e.g.
+/// `4d6kh3` keeps the highest three, `4d6dl1` drops the lowest one.
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum Selection {
+    KeepHighest(u32),
+    KeepLowest(u32),
+    DropHighest(u32),
+    DropLowest(u32),
+}
+
+impl Selection {
+    /// Return the faces retained by this selection. `k` is clamped to the
+    /// number of rolled faces; a keep of 0 yields an empty selection.
+    fn apply(&self, faces: &[u32]) -> Vec<u32> {
+        let count = faces.len();
+        let mut sorted = faces.to_vec();
+        match *self {
+            Selection::KeepHighest(k) => {
+                sorted.sort_unstable_by(|a, b| b.cmp(a));
+                sorted.truncate((k as usize).min(count));
+            }
+            Selection::KeepLowest(k) => {
+                sorted.sort_unstable();
+                sorted.truncate((k as usize).min(count));
+            }
+            Selection::DropHighest(k) => {
+                sorted.sort_unstable_by(|a, b| b.cmp(a));
+                sorted.drain(0..(k as usize).min(count));
+            }
+            Selection::DropLowest(k) => {
+                sorted.sort_unstable();
+                sorted.drain(0..(k as usize).min(count));
+            }
+        }
+        sorted
+    }
+}
+
+impl Display for Selection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Selection::KeepHighest(k) => write!(f, "kh{}", k),
+            Selection::KeepLowest(k) => write!(f, "kl{}", k),
+            Selection::DropHighest(k) => write!(f, "dh{}", k),
+            Selection::DropLowest(k) => write!(f, "dl{}", k),
+        }
+    }
+}
+
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub struct Dice {
     count: u32,
     sides: u32,
+    selection: Option<Selection>,
+    /// A face value that, when rolled, is rerolled once (e.g. `4d6r1`).
+    reroll: Option<u32>,
+    /// Whether a maximum face rolls an extra die, repeating (e.g. `3d6!`).
+    explode: bool,
 }
 
 impl Dice {
-    pub fn new(count: u32, sides: u32) -> Self {
+    /// Construct a die, returning a [`DiceError`] instead of panicking when
+    /// `count` is 0 or `sides` is 1 or fewer.
+    pub fn try_new(count: u32, sides: u32) -> Result<Self, DiceError> {
         if count == 0 {
-            panic!("count must be greater than 0");
+            return Err(DiceError::ZeroCount);
         }
         if sides <= 1 {
-            panic!("sides must be greater than 1");
+            return Err(DiceError::TooFewSides);
         }
-        Self { count, sides }
+        Ok(Self {
+            count,
+            sides,
+            selection: None,
+            reroll: None,
+            explode: false,
+        })
+    }
+
+    /// Panicking constructor kept for backward compatibility; prefer
+    /// [`Dice::try_new`].
+    pub fn new(count: u32, sides: u32) -> Self {
+        Self::try_new(count, sides).unwrap_or_else(|e| panic!("{}", e))
     }
 
     pub fn roll(&self) -> Vec<u32> {
-        let mut rng = rand::thread_rng();
+        self.roll_with(&mut rand::thread_rng())
+    }
+
+    /// Roll using a caller-supplied RNG so results can be reproduced.
+    pub fn roll_with<R: Rng>(&self, rng: &mut R) -> Vec<u32> {
         (0..self.count)
             .map(|_| rng.gen_range(1, self.sides + 1))
             .collect()
     }
+
+    /// Roll the die and apply its reroll and exploding modifiers, returning
+    /// the full expanded face list. Exploding is bounded by
+    /// [`Limit::max_explosions`] so pathological inputs can't run forever.
+    fn expanded_roll<R: Rng>(&self, rng: &mut R, limit: &Limit) -> Result<Vec<u32>, DiceError> {
+        let mut faces = self.roll_with(rng);
+
+        if let Some(target) = self.reroll {
+            for face in faces.iter_mut() {
+                if *face == target {
+                    *face = rng.gen_range(1, self.sides + 1);
+                }
+            }
+        }
+
+        if self.explode {
+            let mut explosions: u64 = 0;
+            let mut i = 0;
+            while i < faces.len() {
+                if faces[i] == self.sides {
+                    if explosions >= limit.max_explosions {
+                        return Err(DiceError::ExplosionLimitExceeded {
+                            max: limit.max_explosions,
+                        });
+                    }
+                    explosions += 1;
+                    let extra = rng.gen_range(1, self.sides + 1);
+                    faces.push(extra);
+                }
+                i += 1;
+            }
+        }
+
+        Ok(faces)
+    }
+
+    /// Sum the faces retained by this die's keep/drop selection (or all of
+    /// them when no selection is present).
+    fn selected_sum(&self, faces: &[u32]) -> i64 {
+        let kept = match self.selection {
+            Some(selection) => selection.apply(faces),
+            None => faces.to_vec(),
+        };
+        kept.iter().map(|&face| i64::from(face)).sum()
+    }
 }
 
 impl Display for Dice {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}d{}", self.count, self.sides)
+        write!(f, "{}d{}", self.count, self.sides)?;
+        if let Some(selection) = self.selection {
+            write!(f, "{}", selection)?;
+        }
+        if let Some(target) = self.reroll {
+            write!(f, "r{}", target)?;
+        }
+        if self.explode {
+            write!(f, "!")?;
+        }
+        Ok(())
+    }
+}
+
+/// An error constructing or evaluating a die.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DiceError {
+    ZeroCount,
+    TooFewSides,
+    CountExceedsLimit { count: u32, max: u32 },
+    SidesExceedsLimit { sides: u32, max: u32 },
+    TooManyDice { total: u64, max: u64 },
+    ExplosionLimitExceeded { max: u64 },
+}
+
+impl Display for DiceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let output = match self {
+            DiceError::ZeroCount => "count must be greater than 0".to_string(),
+            DiceError::TooFewSides => "sides must be greater than 1".to_string(),
+            DiceError::CountExceedsLimit { count, max } => {
+                format!("count {} exceeds the limit of {}", count, max)
+            }
+            DiceError::SidesExceedsLimit { sides, max } => {
+                format!("sides {} exceeds the limit of {}", sides, max)
+            }
+            DiceError::TooManyDice { total, max } => {
+                format!("{} total dice exceeds the limit of {}", total, max)
+            }
+            DiceError::ExplosionLimitExceeded { max } => {
+                format!("exploding dice exceeded the limit of {} extra rolls", max)
+            }
+        };
+        write!(f, "{}", output)
+    }
+}
+
+/// Bounds applied while evaluating an expression so pathological inputs such
+/// as `999999999d999999999` are rejected instead of allocating or hanging.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Limit {
+    pub max_count: u32,
+    pub max_sides: u32,
+    /// Upper bound on the number of dice rolled across the whole expression,
+    /// or `None` to leave the running total unbounded.
+    pub max_total_dice: Option<u64>,
+    /// Upper bound on the number of extra rolls a single exploding die may
+    /// generate before evaluation gives up.
+    pub max_explosions: u64,
+}
+
+impl Default for Limit {
+    fn default() -> Self {
+        Self {
+            max_count: 100_000,
+            max_sides: 100_000,
+            max_total_dice: Some(1_000_000),
+            max_explosions: 100_000,
+        }
+    }
+}
+
+impl Limit {
+    fn check_dice(&self, dice: &Dice) -> Result<(), DiceError> {
+        if dice.count > self.max_count {
+            return Err(DiceError::CountExceedsLimit {
+                count: dice.count,
+                max: self.max_count,
+            });
+        }
+        if dice.sides > self.max_sides {
+            return Err(DiceError::SidesExceedsLimit {
+                sides: dice.sides,
+                max: self.max_sides,
+            });
+        }
+        Ok(())
     }
 }
 
@@ -87,7 +300,10 @@ impl FromStr for Dice {
     type Err = ParseDieError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         lazy_static! {
-            static ref DIE_REGEX: Regex = Regex::new(r#"^(?P<count>\d*)d(?P<sides>\d+)$"#).unwrap();
+            static ref DIE_REGEX: Regex = Regex::new(
+                r#"^(?P<count>\d*)d(?P<sides>\d+)(?P<select>(kh|kl|dh|dl)\d+)?(?P<reroll>r\d+)?(?P<explode>!)?$"#
+            )
+            .unwrap();
         };
         let captures = DIE_REGEX
             .captures(s)
@@ -113,7 +329,38 @@ impl FromStr for Dice {
             .map(|m| m.as_str().parse::<u32>().unwrap())
             .ok_or_else(|| ParseDieError::MissingSides)?;
 
-        Ok(Dice::new(count, sides))
+        let selection = match captures.name("select") {
+            Some(m) => {
+                let raw = m.as_str();
+                let (kind, amount) = raw.split_at(2);
+                let k = amount.parse().map_err(ParseDieError::InvalidSelection)?;
+                Some(match kind {
+                    "kh" => Selection::KeepHighest(k),
+                    "kl" => Selection::KeepLowest(k),
+                    "dh" => Selection::DropHighest(k),
+                    "dl" => Selection::DropLowest(k),
+                    _ => unreachable!("regex only matches known selection kinds"),
+                })
+            }
+            None => None,
+        };
+
+        let reroll = match captures.name("reroll") {
+            Some(m) => Some(
+                m.as_str()[1..]
+                    .parse()
+                    .map_err(ParseDieError::InvalidSelection)?,
+            ),
+            None => None,
+        };
+
+        let explode = captures.name("explode").is_some();
+
+        let mut dice = Dice::try_new(count, sides).map_err(ParseDieError::Invalid)?;
+        dice.selection = selection;
+        dice.reroll = reroll;
+        dice.explode = explode;
+        Ok(dice)
     }
 }
 
@@ -122,6 +369,8 @@ pub enum ParseDieError {
     RegexFailedToCapture,
     InvalidCount(ParseIntError),
     MissingSides,
+    InvalidSelection(ParseIntError),
+    Invalid(DiceError),
 }
 
 impl Display for ParseDieError {
@@ -132,6 +381,478 @@ impl Display for ParseDieError {
             }
             ParseDieError::InvalidCount(c) => format!("parsing failed with error: '{}'", c),
             ParseDieError::MissingSides => "could not match sides".to_string(),
+            ParseDieError::InvalidSelection(c) => {
+                format!("parsing selection failed with error: '{}'", c)
+            }
+            ParseDieError::Invalid(e) => format!("{}", e),
+        };
+        write!(f, "{}", output)
+    }
+}
+
+/// A binary operator in a dice expression.
+///
+/// `Mul` binds tighter than `Add`/`Sub`; all three are left-associative.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum BinOp {
+    Add,
+    Sub,
+    Mul,
+}
+
+impl Display for BinOp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let output = match self {
+            BinOp::Add => "+",
+            BinOp::Sub => "-",
+            BinOp::Mul => "*",
+        };
+        write!(f, "{}", output)
+    }
+}
+
+/// A parsed dice expression such as `2d6 + 3*1d4 - d20`.
+///
+/// Leaves are either a [`Dice`] term or an integer constant; interior nodes
+/// apply a [`BinOp`] to their evaluated children.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Expr {
+    Constant(i64),
+    Term(Dice),
+    Binary {
+        op: BinOp,
+        left: Box<Expr>,
+        right: Box<Expr>,
+    },
+}
+
+/// The rolled faces of a single [`Dice`] term encountered during evaluation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RollBreakdown {
+    pub dice: Dice,
+    pub faces: Vec<u32>,
+}
+
+/// The outcome of evaluating an [`Expr`]: the final value together with the
+/// per-die roll breakdown so callers can still show individual rolls.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EvalResult {
+    pub value: i64,
+    pub rolls: Vec<RollBreakdown>,
+}
+
+impl Expr {
+    /// Roll every die in the expression and fold the tree into a final value,
+    /// applying the default [`Limit`].
+    pub fn evaluate(&self) -> Result<EvalResult, DiceError> {
+        self.evaluate_with_limit(&Limit::default())
+    }
+
+    /// As [`Expr::evaluate`], but reject any die or running dice total that
+    /// exceeds `limit` with a descriptive error.
+    pub fn evaluate_with_limit(&self, limit: &Limit) -> Result<EvalResult, DiceError> {
+        self.evaluate_with_rng(&mut rand::thread_rng(), limit)
+    }
+
+    /// As [`Expr::evaluate_with_limit`], but draw every roll from a
+    /// caller-supplied RNG so an entire evaluation is reproducible.
+    pub fn evaluate_with_rng<R: Rng>(
+        &self,
+        rng: &mut R,
+        limit: &Limit,
+    ) -> Result<EvalResult, DiceError> {
+        let mut rolls = Vec::new();
+        let mut total_dice: u64 = 0;
+        let value = self.eval_into(rng, &mut rolls, &mut total_dice, limit)?;
+        Ok(EvalResult { value, rolls })
+    }
+
+    fn eval_into<R: Rng>(
+        &self,
+        rng: &mut R,
+        rolls: &mut Vec<RollBreakdown>,
+        total_dice: &mut u64,
+        limit: &Limit,
+    ) -> Result<i64, DiceError> {
+        match self {
+            Expr::Constant(n) => Ok(*n),
+            Expr::Term(dice) => {
+                limit.check_dice(dice)?;
+                *total_dice += u64::from(dice.count);
+                if let Some(max) = limit.max_total_dice {
+                    if *total_dice > max {
+                        return Err(DiceError::TooManyDice {
+                            total: *total_dice,
+                            max,
+                        });
+                    }
+                }
+                let faces = dice.expanded_roll(rng, limit)?;
+                let sum = dice.selected_sum(&faces);
+                rolls.push(RollBreakdown {
+                    dice: *dice,
+                    faces,
+                });
+                Ok(sum)
+            }
+            Expr::Binary { op, left, right } => {
+                let left = left.eval_into(rng, rolls, total_dice, limit)?;
+                let right = right.eval_into(rng, rolls, total_dice, limit)?;
+                Ok(match op {
+                    BinOp::Add => left + right,
+                    BinOp::Sub => left - right,
+                    BinOp::Mul => left * right,
+                })
+            }
+        }
+    }
+}
+
+impl Display for Expr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Expr::Constant(n) => write!(f, "{}", n),
+            Expr::Term(dice) => write!(f, "{}", dice),
+            Expr::Binary { op, left, right } => write!(f, "{} {} {}", left, op, right),
+        }
+    }
+}
+
+/// The exact probability mass function of a dice expression, stored as integer
+/// weights over a shared denominator so no precision is lost.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Distribution {
+    outcomes: BTreeMap<i64, u128>,
+    total: u128,
+}
+
+impl Distribution {
+    /// A distribution with all its mass on a single value.
+    fn point(value: i64) -> Self {
+        let mut outcomes = BTreeMap::new();
+        outcomes.insert(value, 1);
+        Self { outcomes, total: 1 }
+    }
+
+    /// The uniform distribution of a single `sides`-sided die, honouring an
+    /// optional reroll-once modifier.
+    fn single_die(sides: u32, reroll: Option<u32>) -> Self {
+        let sides = i64::from(sides);
+        let mut outcomes = BTreeMap::new();
+        match reroll {
+            // With denominator `sides^2`: the mass that would land on the
+            // reroll target is spread uniformly across every face instead. A
+            // target outside `1..=sides` can never come up, so it leaves the
+            // plain uniform distribution unchanged.
+            Some(target) if (1..=sides).contains(&i64::from(target)) => {
+                let target = i64::from(target);
+                for face in 1..=sides {
+                    let base = if face == target { 0 } else { sides as u128 };
+                    outcomes.insert(face, base + 1);
+                }
+                Self {
+                    outcomes,
+                    total: (sides as u128) * (sides as u128),
+                }
+            }
+            _ => {
+                for face in 1..=sides {
+                    outcomes.insert(face, 1);
+                }
+                Self {
+                    outcomes,
+                    total: sides as u128,
+                }
+            }
+        }
+    }
+
+    /// Combine two independent distributions under a binary operator,
+    /// convolving their weights.
+    fn combine<F: Fn(i64, i64) -> i64>(&self, other: &Distribution, op: F) -> Distribution {
+        let mut outcomes: BTreeMap<i64, u128> = BTreeMap::new();
+        for (&a, &wa) in &self.outcomes {
+            for (&b, &wb) in &other.outcomes {
+                *outcomes.entry(op(a, b)).or_insert(0) += wa * wb;
+            }
+        }
+        Distribution {
+            outcomes,
+            total: self.total * other.total,
+        }
+    }
+
+    /// The weighted mean of the distribution.
+    pub fn expected_value(&self) -> f64 {
+        let weighted: f64 = self
+            .outcomes
+            .iter()
+            .map(|(&value, &weight)| value as f64 * weight as f64)
+            .sum();
+        weighted / self.total as f64
+    }
+
+    /// The smallest outcome with non-zero probability.
+    pub fn min(&self) -> i64 {
+        *self
+            .outcomes
+            .keys()
+            .next()
+            .expect("a distribution always has at least one outcome")
+    }
+
+    /// The largest outcome with non-zero probability.
+    pub fn max(&self) -> i64 {
+        *self
+            .outcomes
+            .keys()
+            .next_back()
+            .expect("a distribution always has at least one outcome")
+    }
+
+    /// The probability that a sample is at least `threshold`.
+    pub fn at_least(&self, threshold: i64) -> f64 {
+        let weight: u128 = self
+            .outcomes
+            .range(threshold..)
+            .map(|(_, &weight)| weight)
+            .sum();
+        weight as f64 / self.total as f64
+    }
+
+    /// Iterate outcomes in ascending order together with their probability.
+    pub fn probabilities(&self) -> impl Iterator<Item = (i64, f64)> + '_ {
+        let total = self.total as f64;
+        self.outcomes
+            .iter()
+            .map(move |(&value, &weight)| (value, weight as f64 / total))
+    }
+}
+
+impl Dice {
+    /// The exact probability mass function of this die.
+    ///
+    /// Keep/drop selections and exploding dice have no finite closed-form PMF
+    /// here and are reported as unsupported.
+    pub fn distribution(&self, limit: &Limit) -> Result<Distribution, DistributionError> {
+        limit.check_dice(self).map_err(DistributionError::Limit)?;
+        if self.selection.is_some() {
+            return Err(DistributionError::UnsupportedSelection);
+        }
+        if self.explode {
+            return Err(DistributionError::UnsupportedExplode);
+        }
+        let single = Distribution::single_die(self.sides, self.reroll);
+        let mut dist = Distribution::point(0);
+        for _ in 0..self.count {
+            dist = dist.combine(&single, |a, b| a + b);
+        }
+        Ok(dist)
+    }
+}
+
+impl Expr {
+    /// The exact probability mass function of this expression, combining
+    /// sub-distributions by convolution for `+`/`-`/`*`, applying the default
+    /// [`Limit`].
+    pub fn distribution(&self) -> Result<Distribution, DistributionError> {
+        self.distribution_with_limit(&Limit::default())
+    }
+
+    /// As [`Expr::distribution`], but reject any die that exceeds `limit` so a
+    /// huge `sides`/`count` can't be asked to build an unbounded table.
+    pub fn distribution_with_limit(
+        &self,
+        limit: &Limit,
+    ) -> Result<Distribution, DistributionError> {
+        match self {
+            Expr::Constant(n) => Ok(Distribution::point(*n)),
+            Expr::Term(dice) => dice.distribution(limit),
+            Expr::Binary { op, left, right } => {
+                let left = left.distribution_with_limit(limit)?;
+                let right = right.distribution_with_limit(limit)?;
+                Ok(match op {
+                    BinOp::Add => left.combine(&right, |a, b| a + b),
+                    BinOp::Sub => left.combine(&right, |a, b| a - b),
+                    BinOp::Mul => left.combine(&right, |a, b| a * b),
+                })
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum DistributionError {
+    UnsupportedSelection,
+    UnsupportedExplode,
+    Limit(DiceError),
+}
+
+impl Display for DistributionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DistributionError::UnsupportedSelection => {
+                write!(f, "keep/drop selections have no exact distribution")
+            }
+            DistributionError::UnsupportedExplode => {
+                write!(f, "exploding dice have no exact distribution")
+            }
+            DistributionError::Limit(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+/// A single lexical token of a dice expression.
+enum Token {
+    Number(i64),
+    Dice(Dice),
+    Op(BinOp),
+}
+
+fn tokenize(s: &str) -> Result<Vec<Token>, ParseExprError> {
+    let mut tokens = Vec::new();
+    let mut chars = s.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        match c {
+            ' ' | '\t' => {
+                chars.next();
+            }
+            '+' => {
+                chars.next();
+                tokens.push(Token::Op(BinOp::Add));
+            }
+            '-' => {
+                chars.next();
+                tokens.push(Token::Op(BinOp::Sub));
+            }
+            '*' => {
+                chars.next();
+                tokens.push(Token::Op(BinOp::Mul));
+            }
+            _ => {
+                let mut term = String::new();
+                while let Some(&c) = chars.peek() {
+                    if c.is_ascii_digit() || matches!(c, 'd' | 'k' | 'h' | 'l' | 'r' | '!') {
+                        term.push(c);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                if term.is_empty() {
+                    return Err(ParseExprError::UnexpectedChar(c));
+                }
+                if term.contains('d') {
+                    let dice = term.parse::<Dice>().map_err(ParseExprError::Die)?;
+                    tokens.push(Token::Dice(dice));
+                } else {
+                    let number = term.parse::<i64>().map_err(ParseExprError::InvalidNumber)?;
+                    tokens.push(Token::Number(number));
+                }
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+/// A precedence-climbing parser over a flat token stream.
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn parse_expr(&mut self) -> Result<Expr, ParseExprError> {
+        let mut left = self.parse_term()?;
+        while let Some(&Token::Op(op)) = self.peek() {
+            if op == BinOp::Add || op == BinOp::Sub {
+                self.pos += 1;
+                let right = self.parse_term()?;
+                left = Expr::Binary {
+                    op,
+                    left: Box::new(left),
+                    right: Box::new(right),
+                };
+            } else {
+                break;
+            }
+        }
+        Ok(left)
+    }
+
+    fn parse_term(&mut self) -> Result<Expr, ParseExprError> {
+        let mut left = self.parse_factor()?;
+        while let Some(&Token::Op(BinOp::Mul)) = self.peek() {
+            self.pos += 1;
+            let right = self.parse_factor()?;
+            left = Expr::Binary {
+                op: BinOp::Mul,
+                left: Box::new(left),
+                right: Box::new(right),
+            };
+        }
+        Ok(left)
+    }
+
+    fn parse_factor(&mut self) -> Result<Expr, ParseExprError> {
+        match self.tokens.get(self.pos) {
+            Some(Token::Number(n)) => {
+                let n = *n;
+                self.pos += 1;
+                Ok(Expr::Constant(n))
+            }
+            Some(Token::Dice(dice)) => {
+                let dice = *dice;
+                self.pos += 1;
+                Ok(Expr::Term(dice))
+            }
+            Some(Token::Op(_)) => Err(ParseExprError::UnexpectedOperator),
+            None => Err(ParseExprError::UnexpectedEnd),
+        }
+    }
+}
+
+impl FromStr for Expr {
+    type Err = ParseExprError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tokens = tokenize(s)?;
+        if tokens.is_empty() {
+            return Err(ParseExprError::Empty);
+        }
+        let mut parser = Parser { tokens, pos: 0 };
+        let expr = parser.parse_expr()?;
+        if parser.pos != parser.tokens.len() {
+            return Err(ParseExprError::TrailingTokens);
+        }
+        Ok(expr)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ParseExprError {
+    Empty,
+    UnexpectedChar(char),
+    UnexpectedOperator,
+    UnexpectedEnd,
+    TrailingTokens,
+    InvalidNumber(ParseIntError),
+    Die(ParseDieError),
+}
+
+impl Display for ParseExprError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let output = match self {
+            ParseExprError::Empty => "expression was empty".to_string(),
+            ParseExprError::UnexpectedChar(c) => format!("unexpected character '{}'", c),
+            ParseExprError::UnexpectedOperator => "unexpected operator".to_string(),
+            ParseExprError::UnexpectedEnd => "unexpected end of expression".to_string(),
+            ParseExprError::TrailingTokens => "trailing tokens after expression".to_string(),
+            ParseExprError::InvalidNumber(e) => format!("parsing failed with error: '{}'", e),
+            ParseExprError::Die(e) => format!("{}", e),
         };
         write!(f, "{}", output)
     }
@@ -140,38 +861,78 @@ impl Display for ParseDieError {
 fn main() {
     let opts = Opts::from_args();
 
-    if opts.dice.is_empty() {
+    if opts.expressions.is_empty() {
         eprintln!("Provide some dice to roll")
     }
 
-    for dice in opts.dice {
-        let rolls = dice.roll();
+    if opts.distribution {
+        for expr in opts.expressions {
+            match expr.distribution() {
+                Ok(dist) => {
+                    println!("{}", expr);
+                    for (value, probability) in dist.probabilities() {
+                        println!("  {}: {:.4}", value, probability);
+                    }
+                    println!(
+                        "  mean={:.4} min={} max={}",
+                        dist.expected_value(),
+                        dist.min(),
+                        dist.max()
+                    );
+                }
+                Err(e) => eprintln!("{}: {}", expr, e),
+            }
+        }
+        return;
+    }
+
+    let limit = Limit::default();
+    let mut rng = opts.seed.map(StdRng::seed_from_u64);
+    for expr in opts.expressions {
+        let evaluated = match &mut rng {
+            Some(rng) => expr.evaluate_with_rng(rng, &limit),
+            None => expr.evaluate_with_limit(&limit),
+        };
+        let result = match evaluated {
+            Ok(result) => result,
+            Err(e) => {
+                eprintln!("{}: {}", expr, e);
+                continue;
+            }
+        };
+        let faces: Vec<u32> = result
+            .rolls
+            .iter()
+            .flat_map(|breakdown| breakdown.faces.iter().copied())
+            .collect();
         println!(
             "{} {}",
-            dice,
+            expr,
             match opts.aggregate {
-                None => rolls
-                    .iter()
-                    .map(|roll| roll.to_string())
-                    .collect::<Vec<_>>()
-                    .join(" "),
-                Some(Aggregate::Sum) => format!("{}", rolls.iter().sum::<u32>()),
-                Some(Aggregate::Avg) =>
-                    format!("{}", rolls.iter().sum::<u32>() as f32 / dice.count as f32),
-                Some(Aggregate::Max) => format!(
-                    "{}",
-                    rolls
+                None => format!(
+                    "=> {} [{}]",
+                    result.value,
+                    faces
                         .iter()
-                        .max()
-                        .expect("called aggregate max on empty iter")
-                ),
-                Some(Aggregate::Min) => format!(
-                    "{}",
-                    rolls
-                        .iter()
-                        .min()
-                        .expect("called aggregate min on empty iter")
+                        .map(|roll| roll.to_string())
+                        .collect::<Vec<_>>()
+                        .join(" ")
                 ),
+                // A constant-only expression rolls no dice, so there are no
+                // faces to aggregate; fall back to the evaluated value.
+                Some(Aggregate::Sum) if faces.is_empty() => format!("{}", result.value),
+                Some(Aggregate::Sum) => format!("{}", faces.iter().sum::<u32>()),
+                Some(Aggregate::Avg) if faces.is_empty() => format!("{}", result.value),
+                Some(Aggregate::Avg) =>
+                    format!("{}", faces.iter().sum::<u32>() as f32 / faces.len() as f32),
+                Some(Aggregate::Max) => match faces.iter().max() {
+                    Some(max) => format!("{}", max),
+                    None => format!("{}", result.value),
+                },
+                Some(Aggregate::Min) => match faces.iter().min() {
+                    Some(min) => format!("{}", min),
+                    None => format!("{}", result.value),
+                },
             }
         );
     }
@@ -206,4 +967,174 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_roll_seeded() {
+        // The same seed reproduces the same sequence exactly.
+        let dice = Dice::new(5, 20);
+        let mut a = StdRng::seed_from_u64(42);
+        let mut b = StdRng::seed_from_u64(42);
+        assert_eq!(dice.roll_with(&mut a), dice.roll_with(&mut b));
+
+        let expr = Expr::from_str("2d6 + 3*1d4 - d20").unwrap();
+        let mut a = StdRng::seed_from_u64(7);
+        let mut b = StdRng::seed_from_u64(7);
+        let limit = Limit::default();
+        assert_eq!(
+            expr.evaluate_with_rng(&mut a, &limit).unwrap(),
+            expr.evaluate_with_rng(&mut b, &limit).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_expression() {
+        // `*` binds tighter than `+`/`-`, both left-associative.
+        let expected = Expr::Binary {
+            op: BinOp::Sub,
+            left: Box::new(Expr::Binary {
+                op: BinOp::Add,
+                left: Box::new(Expr::Term(Dice::new(2, 6))),
+                right: Box::new(Expr::Binary {
+                    op: BinOp::Mul,
+                    left: Box::new(Expr::Constant(3)),
+                    right: Box::new(Expr::Term(Dice::new(1, 4))),
+                }),
+            }),
+            right: Box::new(Expr::Term(Dice::new(1, 20))),
+        };
+        assert_eq!(expected, Expr::from_str("2d6 + 3*1d4 - d20").unwrap());
+
+        assert!(Expr::from_str("").is_err());
+        assert!(Expr::from_str("2d6 +").is_err());
+        assert!(Expr::from_str("* 3").is_err());
+    }
+
+    #[test]
+    fn test_parse_selection() {
+        assert_eq!(Some(Selection::KeepHighest(3)), Dice::from_str("4d6kh3").unwrap().selection);
+        assert_eq!(Some(Selection::KeepLowest(1)), Dice::from_str("2d20kl1").unwrap().selection);
+        assert_eq!(Some(Selection::DropLowest(1)), Dice::from_str("4d6dl1").unwrap().selection);
+        assert_eq!(None, Dice::from_str("2d6").unwrap().selection);
+
+        // Keep/drop select the right faces, clamping `k` to the roll count.
+        let faces = [4, 1, 6, 3];
+        assert_eq!(10, Dice::from_str("4d6kh2").unwrap().selected_sum(&faces));
+        assert_eq!(4, Dice::from_str("4d6kl2").unwrap().selected_sum(&faces));
+        assert_eq!(13, Dice::from_str("4d6dl1").unwrap().selected_sum(&faces));
+        assert_eq!(14, Dice::from_str("4d6kh9").unwrap().selected_sum(&faces));
+        assert_eq!(0, Dice::from_str("4d6kh0").unwrap().selected_sum(&faces));
+    }
+
+    #[test]
+    fn test_parse_explode_reroll() {
+        let dice = Dice::from_str("3d6!").unwrap();
+        assert!(dice.explode);
+        assert_eq!(None, dice.reroll);
+
+        let dice = Dice::from_str("4d6r1").unwrap();
+        assert_eq!(Some(1), dice.reroll);
+        assert!(!dice.explode);
+
+        // Exploding keeps rolling while a d2 shows its max, bounded by the cap.
+        let limit = Limit {
+            max_explosions: 3,
+            ..Limit::default()
+        };
+        let dice = Dice::from_str("1d2!").unwrap();
+        let mut rng = StdRng::seed_from_u64(1);
+        for _ in 0..100 {
+            match dice.expanded_roll(&mut rng, &limit) {
+                Ok(faces) => assert!(faces.len() <= 4),
+                Err(DiceError::ExplosionLimitExceeded { max }) => assert_eq!(3, max),
+                Err(e) => panic!("unexpected error: {}", e),
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_modifiers_via_expr() {
+        // Modifier suffixes must survive the CLI path through `Expr::from_str`,
+        // not just `Dice::from_str`.
+        let expr = Expr::from_str("4d6kh3").unwrap();
+        assert_eq!(Expr::Term(Dice::from_str("4d6kh3").unwrap()), expr);
+
+        let expr = Expr::from_str("3d6!").unwrap();
+        assert_eq!(Expr::Term(Dice::from_str("3d6!").unwrap()), expr);
+
+        let expr = Expr::from_str("4d6r1 + 2").unwrap();
+        assert!(matches!(expr, Expr::Binary { .. }));
+    }
+
+    #[test]
+    fn test_distribution() {
+        // A single d6 is uniform; mean 3.5, bounds 1..=6.
+        let dist = Expr::from_str("d6").unwrap().distribution().unwrap();
+        assert_eq!(1, dist.min());
+        assert_eq!(6, dist.max());
+        assert!((dist.expected_value() - 3.5).abs() < 1e-9);
+
+        // 2d6 peaks at 7, ranges 2..=12, and P(X >= 11) = 3/36.
+        let dist = Expr::from_str("2d6").unwrap().distribution().unwrap();
+        assert_eq!(2, dist.min());
+        assert_eq!(12, dist.max());
+        assert!((dist.expected_value() - 7.0).abs() < 1e-9);
+        assert!((dist.at_least(11) - 3.0 / 36.0).abs() < 1e-9);
+
+        // Adding a constant shifts the whole distribution.
+        let dist = Expr::from_str("d6 + 1").unwrap().distribution().unwrap();
+        assert_eq!(2, dist.min());
+        assert_eq!(7, dist.max());
+
+        // Modifiers without a closed form are reported as unsupported.
+        assert!(Expr::from_str("4d6kh3").unwrap().distribution().is_err());
+        assert!(Expr::from_str("3d6!").unwrap().distribution().is_err());
+
+        // A reroll target the die can never show leaves it uniform, so the mass
+        // still sums to exactly 1.
+        let dist = Expr::from_str("1d6r9").unwrap().distribution().unwrap();
+        let mass: f64 = dist.probabilities().map(|(_, p)| p).sum();
+        assert!((mass - 1.0).abs() < 1e-9);
+        assert!((dist.expected_value() - 3.5).abs() < 1e-9);
+
+        // A pathologically large die is rejected rather than building an
+        // unbounded table.
+        assert!(Expr::from_str("d4000000000r1")
+            .unwrap()
+            .distribution()
+            .is_err());
+    }
+
+    #[test]
+    fn test_evaluate_constants() {
+        // A constant-only expression evaluates deterministically.
+        let result = Expr::from_str("2 + 3*4 - 1").unwrap().evaluate().unwrap();
+        assert_eq!(13, result.value);
+        assert!(result.rolls.is_empty());
+    }
+
+    #[test]
+    fn test_limits() {
+        // `try_new` reports the problem instead of panicking.
+        assert_eq!(Err(DiceError::ZeroCount), Dice::try_new(0, 6));
+        assert_eq!(Err(DiceError::TooFewSides), Dice::try_new(4, 1));
+
+        let limit = Limit {
+            max_count: 10,
+            max_sides: 100,
+            max_total_dice: Some(15),
+            max_explosions: 100,
+        };
+        assert!(Expr::from_str("20d6")
+            .unwrap()
+            .evaluate_with_limit(&limit)
+            .is_err());
+        assert!(Expr::from_str("8d6 + 8d6")
+            .unwrap()
+            .evaluate_with_limit(&limit)
+            .is_err());
+        assert!(Expr::from_str("5d6 + 5d6")
+            .unwrap()
+            .evaluate_with_limit(&limit)
+            .is_ok());
+    }
 }